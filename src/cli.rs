@@ -1,3 +1,6 @@
+use crate::report::OutputFormat;
+use crate::tokenizer::Tokenizer;
+use crate::trim::TrimStrategy;
 use clap::Parser;
 use std::path::PathBuf;
 
@@ -5,6 +8,10 @@ use std::path::PathBuf;
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
+    /// Tokenizer engine used to estimate token counts.
+    #[arg(long, value_enum, default_value_t = Tokenizer::Heuristic)]
+    pub tokenizer: Tokenizer,
+
     /// Number of top files to display
     #[arg(long, default_value_t = 9)]
     pub top_file_count: usize,
@@ -13,6 +20,60 @@ pub struct Args {
     #[arg(long, default_value_t = 6)]
     pub top_dir_count: usize,
 
+    /// Render the full parent/child directory hierarchy with proportional
+    /// bars instead of the flat top-directory list.
+    #[arg(long)]
+    pub tree: bool,
+
+    /// With `--tree`, cap the hierarchy at this depth, collapsing deeper
+    /// nodes into their ancestor.
+    #[arg(long)]
+    pub depth: Option<usize>,
+
+    /// Print a per-language breakdown of tokens and lines below the
+    /// existing report sections.
+    #[arg(long)]
+    pub by_language: bool,
+
+    /// Trim the clipboard payload to fit within this many tokens.
+    #[arg(long)]
+    pub max_tokens: Option<usize>,
+
+    /// Strategy used to pick which files fit within `--max-tokens`.
+    #[arg(long, value_enum, default_value_t = TrimStrategy::KeepSmall)]
+    pub trim_strategy: TrimStrategy,
+
+    /// With `--trim-strategy keep-priority`, glob(s) of files to keep first,
+    /// in priority order. Repeatable.
+    #[arg(long)]
+    pub priority_glob: Vec<String>,
+
+    /// Only keep files matching this glob. Repeatable; a file must match at
+    /// least one to be kept.
+    #[arg(long)]
+    pub include: Vec<String>,
+
+    /// Drop files matching this glob. Repeatable.
+    #[arg(long)]
+    pub exclude: Vec<String>,
+
+    /// Only keep files whose name matches this regex.
+    #[arg(long)]
+    pub filter_regex: Option<String>,
+
+    /// Write the formatted payload to this file, in addition to (or instead
+    /// of) the clipboard.
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+
+    /// Stream the formatted payload to standard output.
+    #[arg(long)]
+    pub stdout: bool,
+
+    /// Skip copying the formatted payload to the clipboard entirely.
+    #[arg(long)]
+    pub no_clipboard: bool,
+
     /// Warn about large files by line count (highlight in orange)
     #[arg(long, default_value_t = 300)]
     pub warn_large_files_by_line_count: usize,
@@ -21,6 +82,14 @@ pub struct Args {
     #[arg(long)]
     pub from_clipboard: bool,
 
+    /// Output format for the stats report.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Indent JSON output. Only used with `--format json`.
+    #[arg(long)]
+    pub pretty: bool,
+
     /// Optional path to run `yek` in. If provided, runs `yek --json .` with this as the working directory.
     #[arg(value_name = "PATH")]
     pub path: Option<PathBuf>,
@@ -0,0 +1,113 @@
+use crate::report::OutputFormat;
+
+/// Outcome of the clipboard attempt (or lack of one), fed into
+/// [`plan_delivery`] to decide where the formatted payload ends up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardOutcome {
+    /// `--no-clipboard` was set, so the clipboard was never touched.
+    NotAttempted,
+    /// The clipboard was initialized and the payload was copied to it.
+    Copied,
+    /// `Clipboard::new()` failed. A failure of the copy itself is returned
+    /// as an error instead.
+    Failed,
+}
+
+/// What to actually do once the clipboard attempt is known, given the
+/// `--stdout`/`--output`/`--format` flags. Pure and side-effect free so the
+/// decision logic can be tested without touching the real clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DeliveryActions {
+    /// Print the formatted payload to stdout exactly once: either because
+    /// `--stdout` was passed, or as a text-mode fallback when the clipboard
+    /// failed (never both, so stdout is never written to twice).
+    pub print_payload_to_stdout: bool,
+    /// Warn on stderr that the clipboard-failure fallback was suppressed
+    /// because `--format json` already owns stdout.
+    pub warn_clipboard_failure_suppressed_for_json: bool,
+    /// Print the "✅ Wrote output to <path>" confirmation.
+    pub print_wrote_output_message: bool,
+    /// Print the "✅ Copied to clipboard" confirmation.
+    pub print_copied_message: bool,
+}
+
+/// Decide what gets printed where for the formatted payload. `output_set` is
+/// whether `--output <PATH>` was passed (the actual file write happens in
+/// `main`, since this function has no filesystem access).
+pub fn plan_delivery(
+    clipboard_outcome: ClipboardOutcome,
+    stdout_flag: bool,
+    output_set: bool,
+    format: OutputFormat,
+) -> DeliveryActions {
+    let mut actions = DeliveryActions::default();
+    let mut stdout_already_planned = false;
+
+    if clipboard_outcome == ClipboardOutcome::Failed {
+        if format == OutputFormat::Text {
+            actions.print_payload_to_stdout = true;
+            stdout_already_planned = true;
+        } else {
+            actions.warn_clipboard_failure_suppressed_for_json = true;
+        }
+    }
+
+    if stdout_flag && !stdout_already_planned {
+        actions.print_payload_to_stdout = true;
+    }
+
+    if output_set && format == OutputFormat::Text {
+        actions.print_wrote_output_message = true;
+    }
+
+    if clipboard_outcome == ClipboardOutcome::Copied && format == OutputFormat::Text {
+        actions.print_copied_message = true;
+    }
+
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clipboard_success_with_stdout_prints_payload_only_once() {
+        let actions = plan_delivery(ClipboardOutcome::Copied, true, false, OutputFormat::Text);
+        assert!(actions.print_payload_to_stdout);
+        assert!(actions.print_copied_message);
+        assert!(!actions.warn_clipboard_failure_suppressed_for_json);
+    }
+
+    #[test]
+    fn clipboard_failure_falls_back_to_stdout_in_text_mode() {
+        let actions = plan_delivery(ClipboardOutcome::Failed, false, false, OutputFormat::Text);
+        assert!(actions.print_payload_to_stdout);
+        assert!(!actions.print_copied_message);
+        assert!(!actions.warn_clipboard_failure_suppressed_for_json);
+    }
+
+    #[test]
+    fn clipboard_failure_in_json_mode_does_not_print_raw_payload() {
+        let actions = plan_delivery(ClipboardOutcome::Failed, false, false, OutputFormat::Json);
+        assert!(!actions.print_payload_to_stdout);
+        assert!(actions.warn_clipboard_failure_suppressed_for_json);
+    }
+
+    #[test]
+    fn clipboard_failure_with_explicit_stdout_still_prints_once() {
+        // The fallback and the explicit --stdout both want the payload on
+        // stdout; this must not turn into two prints.
+        let actions = plan_delivery(ClipboardOutcome::Failed, true, false, OutputFormat::Text);
+        assert!(actions.print_payload_to_stdout);
+    }
+
+    #[test]
+    fn no_clipboard_with_output_only_writes_the_file() {
+        let actions = plan_delivery(ClipboardOutcome::NotAttempted, false, true, OutputFormat::Text);
+        assert!(!actions.print_payload_to_stdout);
+        assert!(actions.print_wrote_output_message);
+        assert!(!actions.print_copied_message);
+        assert!(!actions.warn_clipboard_failure_suppressed_for_json);
+    }
+}
@@ -0,0 +1,97 @@
+use anyhow::{Context, Result};
+use glob::Pattern;
+use regex::Regex;
+
+/// Compiled `--include`/`--exclude`/`--filter-regex` filters, applied to a
+/// file's `filename` before it reaches stats, display, or the clipboard.
+#[derive(Debug)]
+pub struct Filters {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    regex: Option<Regex>,
+}
+
+impl Filters {
+    pub fn compile(include: &[String], exclude: &[String], filter_regex: Option<&str>) -> Result<Self> {
+        let include = include
+            .iter()
+            .map(|g| Pattern::new(g).with_context(|| format!("Invalid --include glob: {g}")))
+            .collect::<Result<Vec<_>>>()?;
+        let exclude = exclude
+            .iter()
+            .map(|g| Pattern::new(g).with_context(|| format!("Invalid --exclude glob: {g}")))
+            .collect::<Result<Vec<_>>>()?;
+        let regex = filter_regex
+            .map(|r| Regex::new(r).with_context(|| format!("Invalid --filter-regex: {r}")))
+            .transpose()?;
+        Ok(Self { include, exclude, regex })
+    }
+
+    /// Whether `filename` survives the configured include/exclude globs and
+    /// regex. An empty `--include` list matches everything by default.
+    pub fn matches(&self, filename: &str) -> bool {
+        if !self.include.is_empty() && !self.include.iter().any(|p| p.matches(filename)) {
+            return false;
+        }
+        if self.exclude.iter().any(|p| p.matches(filename)) {
+            return false;
+        }
+        if let Some(regex) = &self.regex {
+            if !regex.is_match(filename) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_filters_match_everything() {
+        let filters = Filters::compile(&[], &[], None).unwrap();
+        assert!(filters.matches("src/main.rs"));
+        assert!(filters.matches("tests/it.rs"));
+    }
+
+    #[test]
+    fn include_glob_keeps_only_matches() {
+        let filters = Filters::compile(&["src/**/*.rs".to_string()], &[], None).unwrap();
+        assert!(filters.matches("src/main.rs"));
+        assert!(filters.matches("src/nested/mod.rs"));
+        assert!(!filters.matches("tests/it.rs"));
+    }
+
+    #[test]
+    fn exclude_glob_drops_matches_even_if_included() {
+        let filters = Filters::compile(
+            &["src/**/*.rs".to_string()],
+            &["src/**/tests/**".to_string()],
+            None,
+        )
+        .unwrap();
+        assert!(filters.matches("src/main.rs"));
+        assert!(!filters.matches("src/tests/it.rs"));
+    }
+
+    #[test]
+    fn filter_regex_drops_names_that_do_not_match() {
+        let filters = Filters::compile(&[], &[], Some("^src/")).unwrap();
+        assert!(filters.matches("src/main.rs"));
+        assert!(!filters.matches("docs/readme.md"));
+    }
+
+    #[test]
+    fn invalid_include_glob_is_a_hard_error() {
+        let err = Filters::compile(&["[".to_string()], &[], None).unwrap_err();
+        assert!(err.to_string().contains("--include"));
+    }
+
+    #[test]
+    fn invalid_filter_regex_is_a_hard_error() {
+        let err = Filters::compile(&[], &[], Some("(")).unwrap_err();
+        assert!(err.to_string().contains("--filter-regex"));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::tree::FileStat;
+use num_format::{Locale, ToFormattedString};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Dedicated bucket for files with no extension or one we don't recognize.
+const OTHER: &str = "Other";
+
+/// Internal extension -> language map. Extend this as new extensions come up.
+fn language_for_extension(ext: Option<&str>) -> &'static str {
+    match ext {
+        Some("rs") => "Rust",
+        Some("py") => "Python",
+        Some("md") => "Markdown",
+        Some("json") => "JSON",
+        Some("toml") => "TOML",
+        Some("js") | Some("mjs") | Some("cjs") => "JavaScript",
+        Some("ts") | Some("tsx") => "TypeScript",
+        Some("go") => "Go",
+        Some("java") => "Java",
+        Some("c") => "C",
+        Some("h") => "C Header",
+        Some("cpp") | Some("cc") | Some("cxx") | Some("hpp") => "C++",
+        Some("html") => "HTML",
+        Some("css") => "CSS",
+        Some("sh") | Some("bash") => "Shell",
+        Some("yaml") | Some("yml") => "YAML",
+        _ => OTHER,
+    }
+}
+
+#[derive(Default)]
+struct LanguageStat {
+    tokens: usize,
+    lines: usize,
+    chars: usize,
+    file_count: usize,
+}
+
+/// Aggregate `(tokens, lines, chars, file_count)` per language, sorted by
+/// token count descending.
+fn aggregate(files: &[FileStat]) -> Vec<(&'static str, LanguageStat)> {
+    let mut by_language: HashMap<&'static str, LanguageStat> = HashMap::new();
+    for file in files {
+        let ext = Path::new(&file.filename)
+            .extension()
+            .and_then(|e| e.to_str());
+        let stat = by_language.entry(language_for_extension(ext)).or_default();
+        stat.tokens += file.tokens;
+        stat.lines += file.lines;
+        stat.chars += file.chars;
+        stat.file_count += 1;
+    }
+
+    let mut sorted: Vec<(&'static str, LanguageStat)> = by_language.into_iter().collect();
+    sorted.sort_by_key(|s| std::cmp::Reverse(s.1.tokens));
+    sorted
+}
+
+/// Print the per-language breakdown table below the existing report sections.
+pub fn print_table(files: &[FileStat]) {
+    println!(
+        "
+By language"
+    );
+    for (language, stat) in aggregate(files) {
+        println!(
+            "- {} (~{} tokens, {} lines, {} chars, {} files)",
+            language,
+            stat.tokens.to_formatted_string(&Locale::en),
+            stat.lines.to_formatted_string(&Locale::en),
+            stat.chars.to_formatted_string(&Locale::en),
+            stat.file_count.to_formatted_string(&Locale::en)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(filename: &str, tokens: usize) -> FileStat {
+        FileStat {
+            filename: filename.to_string(),
+            tokens,
+            lines: tokens,
+            chars: tokens * 4,
+        }
+    }
+
+    #[test]
+    fn recognized_extensions_map_to_their_language() {
+        assert_eq!(language_for_extension(Some("rs")), "Rust");
+        assert_eq!(language_for_extension(Some("py")), "Python");
+        assert_eq!(language_for_extension(Some("md")), "Markdown");
+        assert_eq!(language_for_extension(Some("json")), "JSON");
+    }
+
+    #[test]
+    fn no_extension_and_unknown_extension_both_fall_into_other() {
+        assert_eq!(language_for_extension(None), OTHER);
+        assert_eq!(language_for_extension(Some("xyz")), OTHER);
+    }
+
+    #[test]
+    fn aggregate_sums_per_language_and_sorts_by_tokens_descending() {
+        let files = vec![
+            file("src/a.rs", 10),
+            file("src/b.rs", 5),
+            file("README.md", 2),
+            file("Makefile", 1),
+        ];
+        let aggregated = aggregate(&files);
+
+        assert_eq!(aggregated[0].0, "Rust");
+        assert_eq!(aggregated[0].1.tokens, 15);
+        assert_eq!(aggregated[0].1.file_count, 2);
+
+        assert_eq!(aggregated[1].0, "Markdown");
+        assert_eq!(aggregated[2].0, OTHER);
+        assert_eq!(aggregated[2].1.file_count, 1);
+    }
+}
@@ -9,7 +9,16 @@ use std::path::Path;
 use std::process::Command;
 
 mod cli;
+mod delivery;
+mod filter;
+mod language;
+mod report;
+mod tokenizer;
+mod tree;
+mod trim;
 use crate::cli::Args;
+use crate::delivery::{plan_delivery, ClipboardOutcome};
+use crate::report::{DirReport, FileReport, OutputFormat, Report};
 
 /// A struct that represents a single file's data from the yek JSON output.
 /// We use serde's `derive` macro to automatically handle deserialization.
@@ -17,11 +26,10 @@ use crate::cli::Args;
 struct YekFile {
     filename: String,
     content: String,
-}
-
-/// Approximate token estimation, assuming 4 characters per token.
-pub fn estimate_tokens(text: &str) -> usize {
-    text.chars().count() / 4
+    /// Token count for `content`, filled in after parsing using the
+    /// tokenizer engine selected on the command line.
+    #[serde(skip)]
+    tokens: usize,
 }
 
 fn main() -> Result<()> {
@@ -51,112 +59,246 @@ fn main() -> Result<()> {
     let mut files: Vec<YekFile> = serde_json::from_slice(&output.stdout)
         .context("Failed to parse JSON from `yek` output. Is the format correct?")?;
 
+    // --- Step 2b: Apply --include/--exclude/--filter-regex before any stats are computed ---
+    let filters = filter::Filters::compile(&args.include, &args.exclude, args.filter_regex.as_deref())?;
+    let pre_filter_count = files.len();
+    files.retain(|f| filters.matches(&f.filename));
+    let filtered_out_count = pre_filter_count - files.len();
+
     // --- Step 3: Combine all content and calculate stats ---
     if files.is_empty() {
         println!("✅ No files found in yek output. Nothing to do.");
         return Ok(());
     }
 
-    // Calculate lines for each file and the total raw combined content
-    let mut raw_combined_content = String::new();
+    // Calculate lines and tokens for each file, and the total line count.
+    // The same cached tokenizer engine is used for per-file, per-directory,
+    // and total counts so the whole report stays consistent.
     let mut total_lines = 0;
-    for file in &files {
-        let lines_in_file = file.content.lines().count();
-        total_lines += lines_in_file;
-        raw_combined_content.push_str(&file.content);
+    let mut token_count = 0;
+    for file in &mut files {
+        total_lines += file.content.lines().count();
+        file.tokens = tokenizer::count_tokens(&file.content, args.tokenizer)?;
+        token_count += file.tokens;
     }
 
     let file_count = files.len();
-    let token_count = estimate_tokens(&raw_combined_content);
 
-    println!(
-        "~{} tokens / {} files / {} lines",
-        token_count.to_formatted_string(&Locale::en),
-        file_count.to_formatted_string(&Locale::en),
-        total_lines.to_formatted_string(&Locale::en)
-    );
+    // --- Step 3b: Trim to a token budget, if requested, before assembling the clipboard payload ---
+    let mut trim_report: Option<(Vec<trim::TrimCandidate>, trim::TrimResult)> = None;
+    let included_indices: Vec<usize> = if let Some(max_tokens) = args.max_tokens {
+        let candidates: Vec<trim::TrimCandidate> = files
+            .iter()
+            .map(|f| trim::TrimCandidate {
+                filename: f.filename.clone(),
+                tokens: f.tokens,
+            })
+            .collect();
+        let result = trim::select(&candidates, max_tokens, args.trim_strategy, &args.priority_glob)?;
+        let included = result.included.clone();
+        trim_report = Some((candidates, result));
+        included
+    } else {
+        (0..files.len()).collect()
+    };
 
-    // Prepare formatted combined content for clipboard
+    // Prepare formatted combined content for clipboard, from the included files only
     let mut formatted_combined_content_for_clipboard = String::new();
-    for (i, file) in files.iter().enumerate() {
+    for (i, &idx) in included_indices.iter().enumerate() {
+        let file = &files[idx];
         formatted_combined_content_for_clipboard
             .push_str(&format!(">>>> {}\n{}", file.filename, &file.content));
-        if i < files.len() - 1 {
+        if i < included_indices.len() - 1 {
             formatted_combined_content_for_clipboard.push('\n');
         }
     }
 
-    // --- Step 4: Aggregate and display top N largest directories ---
-    let mut dir_sizes: HashMap<String, (usize, usize)> = HashMap::new(); // Stores (chars, lines)
+    // --- Step 4: Aggregate per-directory (tokens, lines, chars) ---
+    let mut dir_sizes: HashMap<String, (usize, usize, usize)> = HashMap::new(); // (tokens, lines, chars)
     for file in &files {
         if let Some(parent) = Path::new(&file.filename).parent() {
             let dir_name = parent.to_string_lossy().into_owned();
-            let entry = dir_sizes.entry(dir_name).or_insert((0, 0));
-            entry.0 += file.content.len();
-            entry.1 += file.content.lines().count(); // Calculate lines directly
+            let entry = dir_sizes.entry(dir_name).or_insert((0, 0, 0));
+            entry.0 += file.tokens;
+            entry.1 += file.content.lines().count();
+            entry.2 += file.content.len();
         }
     }
+    let mut sorted_dirs: Vec<(String, (usize, usize, usize))> = dir_sizes.into_iter().collect();
+    sorted_dirs.sort_by_key(|d| std::cmp::Reverse(d.1.0));
 
-    let mut sorted_dirs: Vec<(String, (usize, usize))> = dir_sizes.into_iter().collect();
-    sorted_dirs.sort_by(|a, b| b.1.0.cmp(&a.1.0));
+    // Sort files by the length of their content in descending order.
+    files.sort_by_key(|f| std::cmp::Reverse(f.content.len()));
 
-    println!(
-        "
-Largest directories"
-    );
-    for (dir, (size, lines)) in sorted_dirs.iter().take(args.top_dir_count) {
-        if *size == 0 {
-            println!("- {} (empty)", if dir.is_empty() { "." } else { dir });
+    if args.format == OutputFormat::Json {
+        let report = Report {
+            total_tokens: token_count,
+            total_files: file_count,
+            total_lines,
+            directories: sorted_dirs
+                .iter()
+                .map(|(path, (tokens, lines, chars))| DirReport {
+                    path: if path.is_empty() { ".".to_string() } else { path.clone() },
+                    tokens: *tokens,
+                    lines: *lines,
+                    chars: *chars,
+                })
+                .collect(),
+            files: files
+                .iter()
+                .map(|f| FileReport {
+                    filename: f.filename.clone(),
+                    tokens: f.tokens,
+                    lines: f.content.lines().count(),
+                    chars: f.content.len(),
+                    large_file: f.content.lines().count() >= args.warn_large_files_by_line_count,
+                })
+                .collect(),
+            trim: trim_report.as_ref().zip(args.max_tokens).map(
+                |((candidates, result), max_tokens)| report::TrimReport {
+                    max_tokens,
+                    included_files: result.included.len(),
+                    included_tokens: result.included_tokens(candidates),
+                    dropped_files: result.dropped.len(),
+                    dropped_tokens: result.dropped_tokens(candidates),
+                },
+            ),
+        };
+        let json = if args.pretty {
+            serde_json::to_string_pretty(&report)
+        } else {
+            serde_json::to_string(&report)
+        }
+        .context("Failed to serialize report to JSON")?;
+        println!("{json}");
+    } else {
+        let file_stats: Vec<tree::FileStat> = files
+            .iter()
+            .map(|f| tree::FileStat {
+                filename: f.filename.clone(),
+                tokens: f.tokens,
+                lines: f.content.lines().count(),
+                chars: f.content.len(),
+            })
+            .collect();
+
+        if filtered_out_count > 0 {
+            println!(
+                "~{} tokens / {} files / {} lines ({} filtered out)",
+                token_count.to_formatted_string(&Locale::en),
+                file_count.to_formatted_string(&Locale::en),
+                total_lines.to_formatted_string(&Locale::en),
+                filtered_out_count.to_formatted_string(&Locale::en)
+            );
         } else {
-            let tokens = estimate_tokens(&String::from_utf8_lossy(&vec![0; *size])); // Approximate tokens for directory size
             println!(
-                "- {} (~{} tokens, {} lines, {} chars)",
-                if dir.is_empty() { "." } else { dir },
-                tokens.to_formatted_string(&Locale::en),
-                lines.to_formatted_string(&Locale::en),
-                size.to_formatted_string(&Locale::en)
+                "~{} tokens / {} files / {} lines",
+                token_count.to_formatted_string(&Locale::en),
+                file_count.to_formatted_string(&Locale::en),
+                total_lines.to_formatted_string(&Locale::en)
             );
         }
-    }
-
-    // --- Step 5: Find and display the top N largest files ---
-    // Sort files by the length of their content in descending order.
-    files.sort_by(|a, b| b.content.len().cmp(&a.content.len()));
 
-    println!(
-        "
-Largest files"
-    );
-    for file in files.iter().take(args.top_file_count) {
-        if file.content.is_empty() {
-            println!("- {} (empty)", file.filename);
+        println!(
+            "
+Largest directories"
+        );
+        if args.tree {
+            tree::print_tree(&file_stats, args.depth);
         } else {
-            let tokens = estimate_tokens(&file.content);
-            let line_count = file.content.lines().count();
-            let file_info = format!(
-                "- {} (~{} tokens, {} lines, {} chars)",
-                file.filename,
-                tokens.to_formatted_string(&Locale::en),
-                line_count.to_formatted_string(&Locale::en),
-                file.content.len().to_formatted_string(&Locale::en)
-            );
+            for (dir, (tokens, lines, chars)) in sorted_dirs.iter().take(args.top_dir_count) {
+                if *chars == 0 {
+                    println!("- {} (empty)", if dir.is_empty() { "." } else { dir });
+                } else {
+                    println!(
+                        "- {} (~{} tokens, {} lines, {} chars)",
+                        if dir.is_empty() { "." } else { dir },
+                        tokens.to_formatted_string(&Locale::en),
+                        lines.to_formatted_string(&Locale::en),
+                        chars.to_formatted_string(&Locale::en)
+                    );
+                }
+            }
+        }
 
-            if line_count >= args.warn_large_files_by_line_count {
-                println!("{}", file_info.bright_yellow());
+        println!(
+            "
+Largest files"
+        );
+        for file in files.iter().take(args.top_file_count) {
+            if file.content.is_empty() {
+                println!("- {} (empty)", file.filename);
             } else {
-                println!("{file_info}");
+                let line_count = file.content.lines().count();
+                let file_info = format!(
+                    "- {} (~{} tokens, {} lines, {} chars)",
+                    file.filename,
+                    file.tokens.to_formatted_string(&Locale::en),
+                    line_count.to_formatted_string(&Locale::en),
+                    file.content.len().to_formatted_string(&Locale::en)
+                );
+
+                if line_count >= args.warn_large_files_by_line_count {
+                    println!("{}", file_info.bright_yellow());
+                } else {
+                    println!("{file_info}");
+                }
             }
         }
+
+        if args.by_language {
+            language::print_table(&file_stats);
+        }
+
+        if let (Some((candidates, result)), Some(max_tokens)) = (&trim_report, args.max_tokens) {
+            trim::print_report(candidates, result, max_tokens);
+        }
     }
 
-    println!(
-        "
+    // --- Step 6: Deliver the formatted payload to the clipboard, a file, and/or stdout ---
+    let clipboard_outcome = if args.no_clipboard {
+        ClipboardOutcome::NotAttempted
+    } else {
+        match Clipboard::new() {
+            Ok(mut clipboard) => {
+                clipboard
+                    .set_text(formatted_combined_content_for_clipboard.to_string())
+                    .context("Failed to copy content to clipboard.")?;
+                ClipboardOutcome::Copied
+            }
+            Err(err) => {
+                eprintln!("⚠️  Failed to initialize clipboard ({err}); falling back to stdout.");
+                ClipboardOutcome::Failed
+            }
+        }
+    };
+
+    let actions = plan_delivery(clipboard_outcome, args.stdout, args.output.is_some(), args.format);
+
+    if actions.warn_clipboard_failure_suppressed_for_json {
+        eprintln!(
+            "⚠️  Not printing the payload to stdout to keep --format json output parseable; pass --output or --stdout explicitly to retrieve it."
+        );
+    }
+
+    if let Some(path) = &args.output {
+        std::fs::write(path, &formatted_combined_content_for_clipboard)
+            .with_context(|| format!("Failed to write output to {}", path.display()))?;
+        if actions.print_wrote_output_message {
+            println!("✅ Wrote output to {}", path.display());
+        }
+    }
+
+    if actions.print_payload_to_stdout {
+        println!("{formatted_combined_content_for_clipboard}");
+    }
+
+    if actions.print_copied_message {
+        println!(
+            "
 ✅ Copied to clipboard"
-    );
-    let mut clipboard = Clipboard::new().context("Failed to initialize clipboard.")?;
-    clipboard
-        .set_text(formatted_combined_content_for_clipboard.to_string())
-        .context("Failed to copy content to clipboard.")?;
+        );
+    }
 
     Ok(())
 }
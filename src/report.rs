@@ -0,0 +1,114 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt;
+
+/// Output format for the stats report.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text report (the default).
+    Text,
+    /// Machine-readable JSON report via serde_json.
+    Json,
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_possible_value().unwrap().get_name())
+    }
+}
+
+/// A fully structured version of the stats report, serialized to stdout
+/// when `--format json` is passed. Mirrors the sections of the human
+/// text report, but unabridged (no top-N truncation).
+#[derive(Serialize, Debug)]
+pub struct Report {
+    pub total_tokens: usize,
+    pub total_files: usize,
+    pub total_lines: usize,
+    pub directories: Vec<DirReport>,
+    pub files: Vec<FileReport>,
+    /// Present when `--max-tokens` was set, describing what made it into
+    /// the clipboard/output payload vs. what was dropped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trim: Option<TrimReport>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TrimReport {
+    pub max_tokens: usize,
+    pub included_files: usize,
+    pub included_tokens: usize,
+    pub dropped_files: usize,
+    pub dropped_tokens: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DirReport {
+    pub path: String,
+    pub tokens: usize,
+    pub lines: usize,
+    pub chars: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FileReport {
+    pub filename: String,
+    pub tokens: usize,
+    pub lines: usize,
+    pub chars: usize,
+    pub large_file: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report(trim: Option<TrimReport>) -> Report {
+        Report {
+            total_tokens: 30,
+            total_files: 2,
+            total_lines: 10,
+            directories: vec![DirReport {
+                path: "src".to_string(),
+                tokens: 30,
+                lines: 10,
+                chars: 120,
+            }],
+            files: vec![FileReport {
+                filename: "src/main.rs".to_string(),
+                tokens: 30,
+                lines: 10,
+                chars: 120,
+                large_file: false,
+            }],
+            trim,
+        }
+    }
+
+    #[test]
+    fn trim_is_omitted_when_not_set() {
+        let json = serde_json::to_value(sample_report(None)).unwrap();
+        assert!(json["trim"].is_null());
+    }
+
+    #[test]
+    fn trim_is_serialized_into_json() {
+        let trim = TrimReport {
+            max_tokens: 20,
+            included_files: 1,
+            included_tokens: 15,
+            dropped_files: 1,
+            dropped_tokens: 15,
+        };
+        let json = serde_json::to_value(sample_report(Some(trim))).unwrap();
+        assert_eq!(json["trim"]["max_tokens"], 20);
+        assert_eq!(json["trim"]["included_files"], 1);
+        assert_eq!(json["trim"]["dropped_tokens"], 15);
+    }
+
+    #[test]
+    fn output_format_display_matches_the_clap_value_name() {
+        assert_eq!(OutputFormat::Text.to_string(), "text");
+        assert_eq!(OutputFormat::Json.to_string(), "json");
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::fmt;
+use std::sync::OnceLock;
+use tiktoken_rs::CoreBPE;
+
+/// Which engine to use when estimating token counts.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tokenizer {
+    /// Fast `chars / 4` approximation. No merge table is loaded.
+    Heuristic,
+    /// Real BPE using the `cl100k_base` merge ranks (GPT-4 / GPT-3.5-turbo).
+    Cl100k,
+    /// Real BPE using the `o200k_base` merge ranks (GPT-4o).
+    O200k,
+}
+
+impl fmt::Display for Tokenizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_possible_value().unwrap().get_name())
+    }
+}
+
+static CL100K: OnceLock<CoreBPE> = OnceLock::new();
+static O200K: OnceLock<CoreBPE> = OnceLock::new();
+
+fn cl100k() -> Result<&'static CoreBPE> {
+    if CL100K.get().is_none() {
+        let bpe = tiktoken_rs::cl100k_base().context("Failed to load cl100k_base merge ranks")?;
+        let _ = CL100K.set(bpe);
+    }
+    Ok(CL100K.get().unwrap())
+}
+
+fn o200k() -> Result<&'static CoreBPE> {
+    if O200K.get().is_none() {
+        let bpe = tiktoken_rs::o200k_base().context("Failed to load o200k_base merge ranks")?;
+        let _ = O200K.set(bpe);
+    }
+    Ok(O200K.get().unwrap())
+}
+
+/// Count tokens in `text` using the requested tokenizer engine.
+///
+/// `Cl100k` and `O200k` load their merge-rank table once per process (via
+/// `OnceLock`) and reuse it for every call, so per-file, per-directory, and
+/// total counts all run through the same BPE engine instead of drifting
+/// between approximations.
+pub fn count_tokens(text: &str, tokenizer: Tokenizer) -> Result<usize> {
+    match tokenizer {
+        Tokenizer::Heuristic => Ok(text.chars().count() / 4),
+        Tokenizer::Cl100k => Ok(cl100k()?.encode_with_special_tokens(text).len()),
+        Tokenizer::O200k => Ok(o200k()?.encode_with_special_tokens(text).len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heuristic_is_chars_over_four() {
+        assert_eq!(count_tokens("abcdefgh", Tokenizer::Heuristic).unwrap(), 2);
+        assert_eq!(count_tokens("abc", Tokenizer::Heuristic).unwrap(), 0);
+        assert_eq!(count_tokens("", Tokenizer::Heuristic).unwrap(), 0);
+    }
+
+    #[test]
+    fn cl100k_counts_a_known_short_string() {
+        // "Hello world" is two cl100k tokens per OpenAI's own examples.
+        assert_eq!(count_tokens("Hello world", Tokenizer::Cl100k).unwrap(), 2);
+    }
+
+    #[test]
+    fn cl100k_is_repeatable_and_o200k_counts_tokens() {
+        let first = count_tokens("fn main() {}", Tokenizer::Cl100k).unwrap();
+        let second = count_tokens("fn main() {}", Tokenizer::Cl100k).unwrap();
+        assert_eq!(first, second);
+
+        let o200k_count = count_tokens("fn main() {}", Tokenizer::O200k).unwrap();
+        assert!(o200k_count > 0);
+    }
+}
@@ -0,0 +1,172 @@
+use colored::{Color, Colorize};
+use num_format::{Locale, ToFormattedString};
+use std::collections::HashMap;
+use std::path::Path;
+use terminal_size::{terminal_size, Width};
+
+/// Minimal per-file stats needed to build the directory tree. Kept separate
+/// from `YekFile` so this module doesn't need to know about yek's JSON shape.
+pub struct FileStat {
+    pub filename: String,
+    pub tokens: usize,
+    pub lines: usize,
+    pub chars: usize,
+}
+
+/// A directory (or the root) in the hierarchy, holding the aggregated
+/// `(tokens, lines, chars)` of its entire subtree.
+#[derive(Default)]
+struct TreeNode {
+    tokens: usize,
+    lines: usize,
+    chars: usize,
+    children: HashMap<String, TreeNode>,
+}
+
+const DEPTH_COLORS: [Color; 5] = [
+    Color::Cyan,
+    Color::Green,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Blue,
+];
+
+/// Columns reserved for stat text and box-drawing connectors, leaving the
+/// rest of the terminal width for proportional bars.
+const RESERVED_COLUMNS: usize = 50;
+const FALLBACK_TERMINAL_WIDTH: usize = 80;
+
+fn terminal_width() -> usize {
+    terminal_size()
+        .map(|(Width(w), _)| w as usize)
+        .unwrap_or(FALLBACK_TERMINAL_WIDTH)
+}
+
+fn build_tree(files: &[FileStat]) -> TreeNode {
+    let mut root = TreeNode::default();
+    for file in files {
+        root.tokens += file.tokens;
+        root.lines += file.lines;
+        root.chars += file.chars;
+
+        let mut node = &mut root;
+        if let Some(parent) = Path::new(&file.filename).parent() {
+            for component in parent.components() {
+                let name = component.as_os_str().to_string_lossy().into_owned();
+                node = node.children.entry(name).or_default();
+                node.tokens += file.tokens;
+                node.lines += file.lines;
+                node.chars += file.chars;
+            }
+        }
+    }
+    root
+}
+
+fn bar(tokens: usize, longest_tokens: usize, available_width: usize) -> String {
+    if tokens == 0 || longest_tokens == 0 || available_width == 0 {
+        return String::new();
+    }
+    let width = ((tokens as f64 / longest_tokens as f64) * available_width as f64).round() as usize;
+    "█".repeat(width.max(1))
+}
+
+fn print_children(node: &TreeNode, prefix: &str, depth: usize, max_depth: Option<usize>) {
+    if let Some(max) = max_depth {
+        if depth > max {
+            return;
+        }
+    }
+
+    let mut children: Vec<(&String, &TreeNode)> = node.children.iter().collect();
+    children.sort_by_key(|c| std::cmp::Reverse(c.1.tokens));
+
+    let longest_tokens = children.iter().map(|(_, n)| n.tokens).max().unwrap_or(0);
+    let bar_area = terminal_width().saturating_sub(RESERVED_COLUMNS).max(10);
+    let color = DEPTH_COLORS[depth % DEPTH_COLORS.len()];
+
+    let count = children.len();
+    for (i, (name, child)) in children.iter().enumerate() {
+        let is_last = i + 1 == count;
+        let connector = if is_last { "└── " } else { "├── " };
+        let rendered_bar = bar(child.tokens, longest_tokens, bar_area).color(color);
+        println!(
+            "{prefix}{connector}{name} (~{} tokens, {} lines, {} chars) {rendered_bar}",
+            child.tokens.to_formatted_string(&Locale::en),
+            child.lines.to_formatted_string(&Locale::en),
+            child.chars.to_formatted_string(&Locale::en),
+        );
+
+        let child_prefix = format!("{prefix}{}", if is_last { "    " } else { "│   " });
+        print_children(child, &child_prefix, depth + 1, max_depth);
+    }
+}
+
+/// Print the full parent/child directory hierarchy with proportional bars,
+/// box-drawing connectors, and colors that cycle by depth. `max_depth`
+/// collapses deeper nodes into their ancestor (their totals are already
+/// folded in bottom-up, so nothing is lost, just not expanded).
+pub fn print_tree(files: &[FileStat], max_depth: Option<usize>) {
+    let root = build_tree(files);
+    println!(
+        ". (~{} tokens, {} lines, {} chars)",
+        root.tokens.to_formatted_string(&Locale::en),
+        root.lines.to_formatted_string(&Locale::en),
+        root.chars.to_formatted_string(&Locale::en),
+    );
+    print_children(&root, "", 1, max_depth);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(filename: &str, tokens: usize) -> FileStat {
+        FileStat {
+            filename: filename.to_string(),
+            tokens,
+            lines: tokens,
+            chars: tokens * 4,
+        }
+    }
+
+    #[test]
+    fn build_tree_aggregates_bottom_up() {
+        let files = vec![
+            file("src/a.rs", 10),
+            file("src/b.rs", 20),
+            file("src/sub/c.rs", 5),
+            file("README.md", 2),
+        ];
+        let root = build_tree(&files);
+
+        assert_eq!(root.tokens, 37);
+
+        let src = &root.children["src"];
+        assert_eq!(src.tokens, 35);
+
+        let sub = &src.children["sub"];
+        assert_eq!(sub.tokens, 5);
+    }
+
+    #[test]
+    fn build_tree_root_only_file_has_no_directory_children() {
+        let files = vec![file("README.md", 2)];
+        let root = build_tree(&files);
+        assert_eq!(root.tokens, 2);
+        assert!(root.children.is_empty());
+    }
+
+    #[test]
+    fn bar_scales_proportionally_to_the_longest_sibling() {
+        assert_eq!(bar(0, 100, 40).chars().count(), 0);
+        assert_eq!(bar(100, 100, 40).chars().count(), 40);
+        assert_eq!(bar(50, 100, 40).chars().count(), 20);
+    }
+
+    #[test]
+    fn bar_never_empty_for_a_nonzero_share() {
+        // A tiny share should still render at least one bar character.
+        assert_eq!(bar(1, 1_000_000, 40).chars().count(), 1);
+    }
+}
@@ -0,0 +1,192 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use glob::Pattern;
+use num_format::{Locale, ToFormattedString};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Strategy used to pick which files make it into the trimmed clipboard
+/// payload when `--max-tokens` is set.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrimStrategy {
+    /// Fill the budget with as many files as possible, smallest first.
+    KeepSmall,
+    /// Keep files matching `--priority-glob` (in the given order) first,
+    /// then fill any remaining budget with the rest.
+    KeepPriority,
+}
+
+impl fmt::Display for TrimStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_possible_value().unwrap().get_name())
+    }
+}
+
+/// Minimal per-file info needed to decide what fits in the budget.
+pub struct TrimCandidate {
+    pub filename: String,
+    pub tokens: usize,
+}
+
+/// Result of applying a trim strategy: indices into the original file list,
+/// split into what was kept and what was dropped.
+#[derive(Debug)]
+pub struct TrimResult {
+    pub included: Vec<usize>,
+    pub dropped: Vec<usize>,
+}
+
+impl TrimResult {
+    pub fn included_tokens(&self, candidates: &[TrimCandidate]) -> usize {
+        self.included.iter().map(|&i| candidates[i].tokens).sum()
+    }
+
+    pub fn dropped_tokens(&self, candidates: &[TrimCandidate]) -> usize {
+        self.dropped.iter().map(|&i| candidates[i].tokens).sum()
+    }
+}
+
+fn order_for_strategy(
+    candidates: &[TrimCandidate],
+    strategy: TrimStrategy,
+    priority_globs: &[String],
+) -> Result<Vec<usize>> {
+    match strategy {
+        TrimStrategy::KeepSmall => {
+            let mut order: Vec<usize> = (0..candidates.len()).collect();
+            order.sort_by_key(|&i| candidates[i].tokens);
+            Ok(order)
+        }
+        TrimStrategy::KeepPriority => {
+            let patterns: Vec<Pattern> = priority_globs
+                .iter()
+                .map(|g| Pattern::new(g).with_context(|| format!("Invalid --priority-glob: {g}")))
+                .collect::<Result<Vec<_>>>()?;
+
+            let mut order = Vec::with_capacity(candidates.len());
+            let mut taken: HashSet<usize> = HashSet::new();
+            for pattern in &patterns {
+                for (i, candidate) in candidates.iter().enumerate() {
+                    if !taken.contains(&i) && pattern.matches(&candidate.filename) {
+                        order.push(i);
+                        taken.insert(i);
+                    }
+                }
+            }
+            for i in 0..candidates.len() {
+                if !taken.contains(&i) {
+                    order.push(i);
+                }
+            }
+            Ok(order)
+        }
+    }
+}
+
+/// Greedily select files under `max_tokens`, in the order dictated by
+/// `strategy`, stopping before the first file that would exceed the budget.
+pub fn select(
+    candidates: &[TrimCandidate],
+    max_tokens: usize,
+    strategy: TrimStrategy,
+    priority_globs: &[String],
+) -> Result<TrimResult> {
+    let order = order_for_strategy(candidates, strategy, priority_globs)?;
+
+    let mut included = Vec::new();
+    let mut dropped = Vec::new();
+    let mut running_total = 0usize;
+    let mut budget_exceeded = false;
+    for i in order {
+        let tokens = candidates[i].tokens;
+        if !budget_exceeded && running_total + tokens <= max_tokens {
+            running_total += tokens;
+            included.push(i);
+        } else {
+            budget_exceeded = true;
+            dropped.push(i);
+        }
+    }
+    included.sort_unstable();
+    dropped.sort_unstable();
+    Ok(TrimResult { included, dropped })
+}
+
+/// Print a summary of what was kept vs. dropped, with counts and tokens.
+pub fn print_report(candidates: &[TrimCandidate], result: &TrimResult, max_tokens: usize) {
+    println!(
+        "
+Trimmed to fit ~{} tokens",
+        max_tokens.to_formatted_string(&Locale::en)
+    );
+    println!(
+        "- kept {} files (~{} tokens)",
+        result.included.len().to_formatted_string(&Locale::en),
+        result.included_tokens(candidates).to_formatted_string(&Locale::en)
+    );
+    println!(
+        "- dropped {} files (~{} tokens)",
+        result.dropped.len().to_formatted_string(&Locale::en),
+        result.dropped_tokens(candidates).to_formatted_string(&Locale::en)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(filename: &str, tokens: usize) -> TrimCandidate {
+        TrimCandidate {
+            filename: filename.to_string(),
+            tokens,
+        }
+    }
+
+    #[test]
+    fn keep_small_fills_budget_smallest_first() {
+        let candidates = vec![candidate("big.rs", 50), candidate("small.rs", 10), candidate("medium.rs", 20)];
+        let result = select(&candidates, 30, TrimStrategy::KeepSmall, &[]).unwrap();
+
+        assert_eq!(result.included, vec![1, 2]); // small.rs + medium.rs == 30
+        assert_eq!(result.dropped, vec![0]);
+        assert_eq!(result.included_tokens(&candidates), 30);
+        assert_eq!(result.dropped_tokens(&candidates), 50);
+    }
+
+    #[test]
+    fn keep_priority_keeps_matching_globs_first() {
+        let candidates = vec![
+            candidate("src/main.rs", 15),
+            candidate("docs/readme.md", 15),
+            candidate("src/lib.rs", 15),
+        ];
+        let result = select(
+            &candidates,
+            20,
+            TrimStrategy::KeepPriority,
+            &["src/**".to_string()],
+        )
+        .unwrap();
+
+        // Only one of the two src/ files fits in the 20-token budget, but it
+        // must be a src/ file, not docs/readme.md.
+        assert_eq!(result.included.len(), 1);
+        assert!(candidates[result.included[0]].filename.starts_with("src/"));
+    }
+
+    #[test]
+    fn keep_priority_rejects_an_invalid_glob() {
+        let candidates = vec![candidate("a.rs", 1)];
+        let err = select(&candidates, 10, TrimStrategy::KeepPriority, &["[".to_string()])
+            .unwrap_err();
+        assert!(err.to_string().contains("--priority-glob"));
+    }
+
+    #[test]
+    fn max_tokens_of_zero_drops_everything() {
+        let candidates = vec![candidate("a.rs", 1)];
+        let result = select(&candidates, 0, TrimStrategy::KeepSmall, &[]).unwrap();
+        assert!(result.included.is_empty());
+        assert_eq!(result.dropped, vec![0]);
+    }
+}